@@ -0,0 +1,67 @@
+// Normalized PATH handling shared by spawned child processes (ffmpeg, node,
+// pnpm, the folder opener, ...); the direct-path version probes in
+// `check_command` and `check_ffmpeg_specific` still inherit this process's own
+// environment. Consolidates the PATH-guessing that used to be
+// duplicated across `check_command_with_shell`, `check_ffmpeg_specific`, and
+// `open_recordings_folder`. Sandbox detection and full environment cleanup for
+// bundled Linux builds live in the `sandbox` module.
+
+use std::collections::HashSet;
+use std::process::Command;
+
+/// Builds a normalized PATH: common Homebrew/user install locations first (so they
+/// win over same-named system binaries), followed by the existing `PATH`,
+/// deduplicated while preserving order, with any bundle-injected directories
+/// (see `sandbox::bundle_path_roots`) dropped so the bundle's own binaries never
+/// shadow the system's real ones.
+pub fn normalized_path() -> String {
+    let mut seen = HashSet::new();
+    let mut entries: Vec<String> = Vec::new();
+    let bundle_roots = crate::sandbox::bundle_path_roots();
+
+    let mut push_entry = |entry: String| {
+        if entry.is_empty() || !seen.insert(entry.clone()) {
+            return;
+        }
+        if bundle_roots.iter().any(|root| entry.starts_with(root.as_str())) {
+            return;
+        }
+        entries.push(entry);
+    };
+
+    #[cfg(target_os = "macos")]
+    {
+        push_entry("/opt/homebrew/bin".to_string());
+        push_entry("/usr/local/bin".to_string());
+        if let Ok(home) = std::env::var("HOME") {
+            push_entry(format!("{}/.local/bin", home));
+            push_entry(format!("{}/Library/pnpm", home));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if let Ok(home) = std::env::var("HOME") {
+            push_entry(format!("{}/.local/bin", home));
+        }
+        push_entry("/usr/local/bin".to_string());
+    }
+
+    let separator = if cfg!(target_os = "windows") { ';' } else { ':' };
+
+    if let Ok(path_env) = std::env::var("PATH") {
+        for entry in path_env.split(separator) {
+            push_entry(entry.trim().to_string());
+        }
+    }
+
+    entries.join(&separator.to_string())
+}
+
+/// Applies a fully normalized, sandbox-cleaned environment to a [`Command`], so
+/// every spawned tool sees the same resolved `PATH` and never inherits a bundle's
+/// injected library paths, regardless of how this app itself was launched.
+pub fn apply_normalized_env(cmd: &mut Command) {
+    cmd.env_clear();
+    cmd.envs(crate::sandbox::normalized_command_env());
+}
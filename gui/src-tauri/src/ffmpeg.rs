@@ -0,0 +1,77 @@
+// Resolves which FFmpeg binary the recorder should use. Bundling a per-target
+// sidecar removes the install-friction that `get_command_search_paths`/
+// `find_command_path` exist to work around, while keeping that PATH-based lookup
+// as the fallback for setups that don't ship with a bundle (e.g. `cargo tauri
+// dev`). When a bundled sidecar is present it is always used; there is no
+// setting to override it with a system FFmpeg.
+
+use std::path::PathBuf;
+
+use tauri::Manager;
+
+/// Where the FFmpeg binary `start_recording`/`start_stream_recording` should spawn
+/// came from, surfaced to the frontend via `check_system_dependencies`.
+#[derive(Debug, Clone)]
+pub enum FfmpegSource {
+    /// A sidecar binary bundled with this build, at the given absolute path.
+    Bundled(PathBuf),
+    /// Resolved from the user's machine (PATH or a well-known install location).
+    System(String),
+}
+
+impl FfmpegSource {
+    /// The path/command to pass to `Command::new`.
+    pub fn path(&self) -> String {
+        match self {
+            FfmpegSource::Bundled(path) => path.to_string_lossy().to_string(),
+            FfmpegSource::System(path) => path.clone(),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            FfmpegSource::Bundled(_) => "bundled",
+            FfmpegSource::System(_) => "system",
+        }
+    }
+}
+
+/// Prefers a bundled `ffmpeg-<target-triple>` sidecar in the app's resource
+/// directory; only falls back to the existing system PATH search when no such
+/// sidecar is present.
+pub fn resolve_ffmpeg(app: &tauri::AppHandle) -> FfmpegSource {
+    if let Some(sidecar) = bundled_ffmpeg_path(app) {
+        return FfmpegSource::Bundled(sidecar);
+    }
+
+    let system_path = crate::find_command_path("ffmpeg").unwrap_or_else(|| "ffmpeg".to_string());
+    FfmpegSource::System(system_path)
+}
+
+/// Looks for `ffmpeg-<target-triple>[.exe]` next to the app's other bundled
+/// resources, the same location Tauri places external-binary sidecars.
+fn bundled_ffmpeg_path(app: &tauri::AppHandle) -> Option<PathBuf> {
+    let resource_dir = app.path().resource_dir().ok()?;
+    let sidecar_name = format!("ffmpeg-{}{}", target_triple(), std::env::consts::EXE_SUFFIX);
+    let candidate = resource_dir.join(sidecar_name);
+
+    if candidate.is_file() {
+        Some(candidate)
+    } else {
+        None
+    }
+}
+
+/// The Rust target triple for the binaries we bundle. Covers the platforms this
+/// app actually ships for; anything else falls back to the system PATH search.
+fn target_triple() -> &'static str {
+    match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "aarch64-apple-darwin",
+        ("macos", "x86_64") => "x86_64-apple-darwin",
+        ("linux", "aarch64") => "aarch64-unknown-linux-gnu",
+        ("linux", "x86_64") => "x86_64-unknown-linux-gnu",
+        ("windows", "x86_64") => "x86_64-pc-windows-msvc",
+        ("windows", "aarch64") => "aarch64-pc-windows-msvc",
+        _ => "unknown",
+    }
+}
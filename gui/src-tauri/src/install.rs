@@ -0,0 +1,316 @@
+// Generalizes dependency installation into an explicit plan of steps (Node.js,
+// pnpm, FFmpeg, Playwright browsers), each resolved against whichever package
+// manager actually runs on this machine — probed at runtime rather than assumed
+// from `cfg!(target_os = ...)`, since e.g. a Linux box might have `dnf` but not
+// `apt` — and run as its own child process so progress can stream back to the
+// frontend instead of the call blocking until everything finishes.
+
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+
+use serde::Serialize;
+use tauri::Emitter;
+
+/// One unit of work in an [`InstallPlan`].
+pub struct InstallStep {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    /// Working directory the step should run in, e.g. the project root for the
+    /// Playwright browsers step. `None` runs in the app's current directory.
+    pub cwd: Option<String>,
+}
+
+/// The ordered set of steps needed to get this machine ready to record, built for
+/// whichever package manager [`InstallPlan::detect`] found.
+pub struct InstallPlan {
+    pub manager: String,
+    pub steps: Vec<InstallStep>,
+}
+
+/// One line of output (or a step's start/end) emitted on the `install://progress`
+/// event while a plan runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallProgress {
+    pub step: String,
+    pub line: Option<String>,
+    pub stream: Option<&'static str>, // "stdout" | "stderr"
+    pub status: Option<&'static str>, // "running" | "success" | "failed"
+    pub exit_code: Option<i32>,
+}
+
+fn playwright_step(project_root: &str) -> InstallStep {
+    let pnpm_path = crate::find_command_path("pnpm").unwrap_or_else(|| "pnpm".to_string());
+    InstallStep {
+        name: "Playwright Browsers".to_string(),
+        command: pnpm_path,
+        args: vec!["run".to_string(), "install-browsers".to_string()],
+        cwd: Some(project_root.to_string()),
+    }
+}
+
+/// Builds just the FFmpeg step for this machine's package manager, for the
+/// standalone "install FFmpeg only" command rather than the full [`InstallPlan`].
+fn ffmpeg_step() -> Result<InstallStep, String> {
+    #[cfg(target_os = "macos")]
+    {
+        let brew = crate::BrewVariant::detect()
+            .ok_or("Homebrew not found. Install it from https://brew.sh first.")?;
+        return Ok(InstallStep {
+            name: "FFmpeg".to_string(),
+            command: brew.brew_binary().to_string(),
+            args: vec!["install".to_string(), "ffmpeg".to_string()],
+            cwd: None,
+        });
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (installer, args): (&str, Vec<&str>) = if crate::check_command("winget", &["--version"]).0 {
+            ("winget", vec!["install", "-e", "--id", "Gyan.FFmpeg"])
+        } else if crate::check_command("choco", &["--version"]).0 {
+            ("choco", vec!["install", "ffmpeg", "-y"])
+        } else {
+            return Err("Neither winget nor choco was found.".to_string());
+        };
+        return Ok(InstallStep {
+            name: "FFmpeg".to_string(),
+            command: installer.to_string(),
+            args: args.into_iter().map(String::from).collect(),
+            cwd: None,
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let args: Vec<String> = if crate::check_command("apt", &["--version"]).0 {
+            vec!["apt".to_string(), "install".to_string(), "-y".to_string(), "ffmpeg".to_string()]
+        } else if crate::check_command("dnf", &["--version"]).0 {
+            vec!["dnf".to_string(), "install".to_string(), "-y".to_string(), "ffmpeg".to_string()]
+        } else if crate::check_command("pacman", &["--version"]).0 {
+            vec!["pacman".to_string(), "-S".to_string(), "--noconfirm".to_string(), "ffmpeg".to_string()]
+        } else {
+            return Err("No supported package manager (apt/dnf/pacman) found.".to_string());
+        };
+        return Ok(InstallStep {
+            name: "FFmpeg".to_string(),
+            command: "sudo".to_string(),
+            args,
+            cwd: None,
+        });
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    Err("Automatic FFmpeg installation is not supported on this platform.".to_string())
+}
+
+/// A single-step plan that installs only FFmpeg, streaming its progress the same
+/// way the full [`InstallPlan`] does.
+pub fn ffmpeg_only_plan() -> Result<InstallPlan, String> {
+    let step = ffmpeg_step()?;
+    Ok(InstallPlan {
+        manager: step.command.clone(),
+        steps: vec![step],
+    })
+}
+
+impl InstallPlan {
+    /// Builds the plan for this machine, or an error describing why no supported
+    /// package manager could be found.
+    pub fn detect(project_root: &str) -> Result<InstallPlan, String> {
+        #[cfg(target_os = "macos")]
+        {
+            let brew = crate::BrewVariant::detect()
+                .ok_or("Homebrew not found. Install it from https://brew.sh first.")?;
+            let brew_bin = brew.brew_binary().to_string();
+
+            // The formula name brew expects is not always derivable from the
+            // display name (`"Node.js"` installs as `node`), so pass it explicitly.
+            let brew_install = |display: &str, formula: &str| InstallStep {
+                name: display.to_string(),
+                command: brew_bin.clone(),
+                args: vec!["install".to_string(), formula.to_string()],
+                cwd: None,
+            };
+
+            return Ok(InstallPlan {
+                manager: format!("Homebrew ({})", brew_bin),
+                steps: vec![
+                    brew_install("Node.js", "node"),
+                    brew_install("pnpm", "pnpm"),
+                    brew_install("FFmpeg", "ffmpeg"),
+                    playwright_step(project_root),
+                ],
+            });
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            if crate::check_command("winget", &["--version"]).0 {
+                return Ok(InstallPlan {
+                    manager: "winget".to_string(),
+                    steps: vec![
+                        InstallStep {
+                            name: "Node.js".to_string(),
+                            command: "winget".to_string(),
+                            args: vec!["install".to_string(), "-e".to_string(), "--id".to_string(), "OpenJS.NodeJS.LTS".to_string()],
+                            cwd: None,
+                        },
+                        InstallStep {
+                            name: "pnpm".to_string(),
+                            command: "npm".to_string(),
+                            args: vec!["install".to_string(), "-g".to_string(), "pnpm".to_string()],
+                            cwd: None,
+                        },
+                        InstallStep {
+                            name: "FFmpeg".to_string(),
+                            command: "winget".to_string(),
+                            args: vec!["install".to_string(), "-e".to_string(), "--id".to_string(), "Gyan.FFmpeg".to_string()],
+                            cwd: None,
+                        },
+                        playwright_step(project_root),
+                    ],
+                });
+            }
+
+            if crate::check_command("choco", &["--version"]).0 {
+                return Ok(InstallPlan {
+                    manager: "choco".to_string(),
+                    steps: vec![
+                        InstallStep {
+                            name: "Node.js".to_string(),
+                            command: "choco".to_string(),
+                            args: vec!["install".to_string(), "nodejs-lts".to_string(), "-y".to_string()],
+                            cwd: None,
+                        },
+                        InstallStep {
+                            name: "pnpm".to_string(),
+                            command: "npm".to_string(),
+                            args: vec!["install".to_string(), "-g".to_string(), "pnpm".to_string()],
+                            cwd: None,
+                        },
+                        InstallStep {
+                            name: "FFmpeg".to_string(),
+                            command: "choco".to_string(),
+                            args: vec!["install".to_string(), "ffmpeg".to_string(), "-y".to_string()],
+                            cwd: None,
+                        },
+                        playwright_step(project_root),
+                    ],
+                });
+            }
+
+            return Err("Neither winget nor choco was found.".to_string());
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let (manager, package_args): (&str, fn(&str) -> Vec<String>) = if crate::check_command("apt", &["--version"]).0 {
+                ("apt", |pkg: &str| vec!["apt".to_string(), "install".to_string(), "-y".to_string(), pkg.to_string()])
+            } else if crate::check_command("dnf", &["--version"]).0 {
+                ("dnf", |pkg: &str| vec!["dnf".to_string(), "install".to_string(), "-y".to_string(), pkg.to_string()])
+            } else if crate::check_command("pacman", &["--version"]).0 {
+                ("pacman", |pkg: &str| vec!["pacman".to_string(), "-S".to_string(), "--noconfirm".to_string(), pkg.to_string()])
+            } else {
+                return Err("No supported package manager (apt/dnf/pacman) found.".to_string());
+            };
+
+            return Ok(InstallPlan {
+                manager: manager.to_string(),
+                steps: vec![
+                    InstallStep { name: "Node.js".to_string(), command: "sudo".to_string(), args: package_args("nodejs"), cwd: None },
+                    InstallStep {
+                        name: "pnpm".to_string(),
+                        command: "sh".to_string(),
+                        args: vec!["-c".to_string(), "curl -fsSL https://get.pnpm.io/install.sh | sh -".to_string()],
+                        cwd: None,
+                    },
+                    InstallStep { name: "FFmpeg".to_string(), command: "sudo".to_string(), args: package_args("ffmpeg"), cwd: None },
+                    playwright_step(project_root),
+                ],
+            });
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+        Err("Automatic dependency installation is not supported on this platform.".to_string())
+    }
+
+    /// Runs every step in order, streaming each line of output as an
+    /// `install://progress` event, and stops at the first step that fails.
+    pub fn run(self, app: &tauri::AppHandle) -> Result<(), String> {
+        for step in self.steps {
+            emit(app, &step.name, None, None, Some("running"), None);
+
+            let mut cmd = Command::new(&step.command);
+            cmd.args(&step.args).stdout(Stdio::piped()).stderr(Stdio::piped());
+            if let Some(cwd) = &step.cwd {
+                cmd.current_dir(cwd);
+            }
+            crate::env::apply_normalized_env(&mut cmd);
+
+            let mut child = cmd
+                .spawn()
+                .map_err(|e| format!("Failed to start {} install step: {}", step.name, e))?;
+
+            let stdout_handle = pump_lines(app.clone(), step.name.clone(), "stdout", child.stdout.take());
+            let stderr_handle = pump_lines(app.clone(), step.name.clone(), "stderr", child.stderr.take());
+
+            let status = child
+                .wait()
+                .map_err(|e| format!("Failed to wait for {} install step: {}", step.name, e))?;
+
+            if let Some(handle) = stdout_handle {
+                let _ = handle.join();
+            }
+            if let Some(handle) = stderr_handle {
+                let _ = handle.join();
+            }
+
+            let exit_code = status.code();
+            if status.success() {
+                emit(app, &step.name, None, None, Some("success"), exit_code);
+            } else {
+                emit(app, &step.name, None, None, Some("failed"), exit_code);
+                return Err(format!("{} install step failed (exit code {:?})", step.name, exit_code));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads `pipe` line by line on its own thread, forwarding each line as an
+/// `install://progress` event, so stdout and stderr drain concurrently and a
+/// slow/chatty child never deadlocks on a full pipe buffer.
+fn pump_lines<R>(app: tauri::AppHandle, step: String, stream: &'static str, pipe: Option<R>) -> Option<std::thread::JoinHandle<()>>
+where
+    R: std::io::Read + Send + 'static,
+{
+    let pipe = pipe?;
+    Some(std::thread::spawn(move || {
+        let reader = BufReader::new(pipe);
+        for line in reader.lines().map_while(Result::ok) {
+            emit(&app, &step, Some(line), Some(stream), None, None);
+        }
+    }))
+}
+
+fn emit(
+    app: &tauri::AppHandle,
+    step: &str,
+    line: Option<String>,
+    stream: Option<&'static str>,
+    status: Option<&'static str>,
+    exit_code: Option<i32>,
+) {
+    let _ = app.emit(
+        "install://progress",
+        InstallProgress {
+            step: step.to_string(),
+            line,
+            stream,
+            status,
+            exit_code,
+        },
+    );
+}
@@ -1,16 +1,114 @@
+mod env;
+mod ffmpeg;
+mod install;
+mod runner;
+mod sandbox;
+
 use serde::{Deserialize, Serialize};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::path::PathBuf;
-use std::env;
 use uuid::Uuid;
 use std::collections::HashMap;
 use std::sync::{LazyLock, Mutex};
+use std::time::SystemTime;
 
-// Global storage for active recording sessions
-static ACTIVE_RECORDINGS: LazyLock<Mutex<HashMap<String, std::process::Child>>> = LazyLock::new(|| {
+// Global storage for active recording sessions, each owned by a `RecordingRunner`
+// that tracks real process-exit state via non-blocking `try_wait` instead of just
+// "is the session id still in the map".
+static ACTIVE_RECORDINGS: LazyLock<Mutex<HashMap<String, runner::RecordingRunner>>> = LazyLock::new(|| {
     Mutex::new(HashMap::new())
 });
 
+// A live "stream" session: the Playwright recorder's raw frames are piped straight
+// into an FFmpeg encoder publishing FLV to an RTMP endpoint, instead of either side
+// touching disk. The Rust side owns both children the same way `ACTIVE_RECORDINGS`
+// owns the file-based recorder, with the encoder's stderr drained into a ring
+// buffer the same way a `RecordingRunner` would.
+struct ActiveStream {
+    recorder: std::process::Child,
+    ffmpeg: std::process::Child,
+    start_time: SystemTime,
+    log: runner::LogRing,
+    log_handle: Option<std::thread::JoinHandle<()>>,
+    endpoint: String,
+    // Background thread pumping recorder stdout into ffmpeg stdin.
+    pump_handle: Option<std::thread::JoinHandle<()>>,
+    // The exit status plus the duration computed once, at the moment the
+    // process was first reaped — see `runner::RecordingRunner`'s identical field.
+    terminal: Option<(std::process::ExitStatus, f64)>,
+    terminal_since: Option<std::time::Instant>,
+}
+
+static ACTIVE_STREAMS: LazyLock<Mutex<HashMap<String, ActiveStream>>> = LazyLock::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+// Sessions are kept in `ACTIVE_RECORDINGS`/`ACTIVE_STREAMS` past their terminal
+// status so a late frontend poll still sees the final result; without this sweep
+// nothing would reap them afterwards — an unbounded leak of `Child`s, joined log-pump
+// threads, and ring buffers for the lifetime of the app. Swept opportunistically
+// whenever a new recording starts, rather than on a timer, to avoid adding a
+// background thread just for cleanup.
+fn evict_stale_sessions() {
+    if let Ok(mut recordings) = ACTIVE_RECORDINGS.lock() {
+        recordings.retain(|_, runner| !runner.is_stale());
+    }
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.retain(|_, stream| {
+            stream
+                .terminal_since
+                .is_none_or(|since| since.elapsed() < runner::SESSION_RETENTION)
+        });
+    }
+}
+
+fn elapsed_secs(start: SystemTime) -> f64 {
+    SystemTime::now()
+        .duration_since(start)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Which Homebrew installation is present on this machine, if any.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BrewVariant {
+    /// Apple Silicon Homebrew at `/opt/homebrew`.
+    MacArm,
+    /// Intel Homebrew at `/usr/local`.
+    MacIntel,
+    /// `brew` resolves on PATH but not at either well-known prefix.
+    Path,
+}
+
+impl BrewVariant {
+    fn brew_binary(&self) -> &'static str {
+        match self {
+            BrewVariant::MacArm => "/opt/homebrew/bin/brew",
+            BrewVariant::MacIntel => "/usr/local/bin/brew",
+            BrewVariant::Path => "brew",
+        }
+    }
+
+    /// Probe which brew binaries actually exist and pick the one matching this
+    /// machine's architecture, preferring Apple Silicon's when both are present
+    /// on an Apple Silicon build; if only one exists, that one is used.
+    fn detect() -> Option<BrewVariant> {
+        let arm_exists = std::fs::metadata("/opt/homebrew/bin/brew").is_ok();
+        let intel_exists = std::fs::metadata("/usr/local/bin/brew").is_ok();
+        let is_arm = std::env::consts::ARCH == "aarch64";
+
+        if arm_exists && (is_arm || !intel_exists) {
+            Some(BrewVariant::MacArm)
+        } else if intel_exists {
+            Some(BrewVariant::MacIntel)
+        } else if check_command("brew", &["--version"]).0 {
+            Some(BrewVariant::Path)
+        } else {
+            None
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SystemDependency {
     name: String,
@@ -23,7 +121,7 @@ pub struct SystemDependency {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RecordingOptions {
     figma_url: String,
-    recording_mode: String, // "video" or "frames"
+    recording_mode: String, // "video", "frames", or "stream"
     quality: String,
     custom_width: Option<u32>,
     custom_height: Option<u32>,
@@ -31,6 +129,9 @@ pub struct RecordingOptions {
     format: String,
     frame_rate: Option<u32>,
     wait_for_canvas: bool,
+    browser_engine: Option<String>, // "chromium" | "firefox" | "webkit", defaults to chromium
+    browser_channel: Option<String>, // e.g. "chrome", "msedge", "webkit-technology-preview"
+    rtmp_url: Option<String>, // required when recording_mode == "stream"
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -73,6 +174,29 @@ pub struct DependencyStatus {
     browsers: DependencyInfo,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DependencyReport {
+    name: String,
+    path: Option<String>,
+    install_source: Option<String>, // e.g. "Homebrew (Apple Silicon)", "nvm", "System"
+    version: Option<String>,
+    min_version: Option<String>,
+    status: String, // "pass" | "warn" | "fail"
+    hint: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    os: String,
+    arch: String,
+    app_version: String,
+    project_root: String,
+    project_version: Option<String>,
+    normalized_path: String,
+    playwright_cache_dir: String,
+    items: Vec<DependencyReport>,
+}
+
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
 fn greet(name: &str) -> String {
@@ -80,7 +204,7 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn check_system_dependencies() -> Result<InstallationStatus, String> {
+async fn check_system_dependencies(app: tauri::AppHandle) -> Result<InstallationStatus, String> {
     let mut dependencies = Vec::new();
 
     // Check Node.js with multiple methods
@@ -109,37 +233,48 @@ async fn check_system_dependencies() -> Result<InstallationStatus, String> {
         install_url: Some("https://pnpm.io/installation".to_string()),
     });
 
-    // Check FFmpeg with multiple methods
-    let ffmpeg_check = check_command("ffmpeg", &["-version"]);
-    
-    // Add debug logging for FFmpeg detection
-    println!("FFmpeg detection result: installed={}, version={:?}", ffmpeg_check.0, ffmpeg_check.1);
-    
-    if !ffmpeg_check.0 {
-        // If not detected, let's log some debug info
-        if let Some(path) = find_command_path("ffmpeg") {
-            println!("FFmpeg path found: {}", path);
-        } else {
-            println!("FFmpeg path not found via find_command_path");
-        }
-        
-        // Check specific paths
-        let ffmpeg_paths = get_command_search_paths("ffmpeg");
-        println!("Checking {} FFmpeg paths:", ffmpeg_paths.len());
-        for path in &ffmpeg_paths[..std::cmp::min(5, ffmpeg_paths.len())] {
-            if std::fs::metadata(path).is_ok() {
-                println!("  ✓ Found: {}", path);
-            } else {
-                println!("  ✗ Missing: {}", path);
+    // FFmpeg: prefer the bundled sidecar (no install-friction) and only fall back
+    // to the system PATH search when this build doesn't ship one.
+    let ffmpeg_source = ffmpeg::resolve_ffmpeg(&app);
+    let (ffmpeg_installed, ffmpeg_version, ffmpeg_install_command) = match &ffmpeg_source {
+        ffmpeg::FfmpegSource::Bundled(_) => (true, Some("bundled".to_string()), None),
+        ffmpeg::FfmpegSource::System(_) => {
+            let ffmpeg_check = check_command("ffmpeg", &["-version"]);
+            println!("FFmpeg detection result: installed={}, version={:?}", ffmpeg_check.0, ffmpeg_check.1);
+
+            if !ffmpeg_check.0 {
+                // If not detected, let's log some debug info
+                if let Some(path) = find_command_path("ffmpeg") {
+                    println!("FFmpeg path found: {}", path);
+                } else {
+                    println!("FFmpeg path not found via find_command_path");
+                }
+
+                // Check specific paths
+                let ffmpeg_paths = get_command_search_paths("ffmpeg");
+                println!("Checking {} FFmpeg paths:", ffmpeg_paths.len());
+                for path in &ffmpeg_paths[..std::cmp::min(5, ffmpeg_paths.len())] {
+                    if std::fs::metadata(path).is_ok() {
+                        println!("  ✓ Found: {}", path);
+                    } else {
+                        println!("  ✗ Missing: {}", path);
+                    }
+                }
             }
+
+            (
+                ffmpeg_check.0,
+                ffmpeg_check.1.as_ref().and_then(|v| v.lines().next().map(|s| s.to_string())),
+                get_ffmpeg_install_command(),
+            )
         }
-    }
-    
+    };
+
     dependencies.push(SystemDependency {
         name: "FFmpeg".to_string(),
-        installed: ffmpeg_check.0,
-        version: ffmpeg_check.1.as_ref().and_then(|v| v.lines().next().map(|s| s.to_string())),
-        install_command: get_ffmpeg_install_command(),
+        installed: ffmpeg_installed,
+        version: ffmpeg_version,
+        install_command: ffmpeg_install_command,
         install_url: Some("https://ffmpeg.org/download.html".to_string()),
     });
 
@@ -164,39 +299,45 @@ async fn check_system_dependencies() -> Result<InstallationStatus, String> {
 }
 
 #[tauri::command]
-async fn install_dependencies() -> Result<String, String> {
+async fn install_dependencies(app: tauri::AppHandle) -> Result<String, String> {
     let project_path = get_project_root_path();
-    
-    // Find pnpm executable
-    let pnpm_path = find_command_path("pnpm")
-        .ok_or("pnpm not found. Please install pnpm first.")?;
-    
-    // Change to project directory and install dependencies
-    let output = Command::new(pnpm_path)
-        .args(&["install"])
-        .current_dir(&project_path)
-        .output()
-        .map_err(|e| format!("Failed to run pnpm install: {}", e))?;
 
-    if output.status.success() {
-        Ok("Dependencies installed successfully".to_string())
-    } else {
-        Err(format!("pnpm install failed: {}", String::from_utf8_lossy(&output.stderr)))
-    }
+    let plan = install::InstallPlan::detect(&project_path)?;
+    let manager = plan.manager.clone();
+    plan.run(&app)?;
+
+    Ok(format!("Dependencies installed successfully via {}", manager))
+}
+
+#[tauri::command]
+async fn install_ffmpeg(app: tauri::AppHandle) -> Result<String, String> {
+    // Delegates to the same streaming `install::InstallPlan` machinery as
+    // `install_dependencies`/chunk1-4, instead of a blocking `Command::output()`
+    // call, so progress actually streams back via `install://progress`.
+    let plan = install::ffmpeg_only_plan()?;
+    plan.run(&app)?;
+    Ok("FFmpeg installed successfully".to_string())
 }
 
 #[tauri::command]
-async fn install_playwright_browsers() -> Result<String, String> {
+async fn install_playwright_browsers(channel: Option<String>) -> Result<String, String> {
     let project_path = get_project_root_path();
-    
+
     // Find pnpm executable
     let pnpm_path = find_command_path("pnpm")
         .ok_or("pnpm not found. Please install pnpm first.")?;
-    
-    let output = Command::new(pnpm_path)
-        .args(&["run", "install-browsers"])
-        .current_dir(&project_path)
-        .output()
+
+    // Pulling a single channel (e.g. the WebKit technology-preview build) uses the
+    // underlying `playwright install` CLI directly; installing everything keeps
+    // using the project's existing `install-browsers` script.
+    let mut cmd = Command::new(&pnpm_path);
+    match &channel {
+        Some(channel) => cmd.args(&["exec", "playwright", "install", channel]),
+        None => cmd.args(&["run", "install-browsers"]),
+    };
+    cmd.current_dir(&project_path);
+    env::apply_normalized_env(&mut cmd);
+    let output = cmd.output()
         .map_err(|e| format!("Failed to install browsers: {}", e))?;
 
     if output.status.success() {
@@ -207,9 +348,11 @@ async fn install_playwright_browsers() -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn start_recording(options: RecordingOptions) -> Result<RecordingSession, String> {
+async fn start_recording(app: tauri::AppHandle, options: RecordingOptions) -> Result<RecordingSession, String> {
     println!("Starting recording with options: {:?}", options);
-    
+
+    evict_stale_sessions();
+
     // Create a unique session ID
     let session_id = Uuid::new_v4().to_string();
     let start_time = std::time::SystemTime::now()
@@ -244,7 +387,13 @@ async fn start_recording(options: RecordingOptions) -> Result<RecordingSession,
         println!("Already in project root: {}", current_dir.display());
         &current_dir
     };
-    
+
+    let ffmpeg_source = ffmpeg::resolve_ffmpeg(&app);
+
+    if options.recording_mode == "stream" {
+        return start_stream_recording(&ffmpeg_source, &options, project_root, session_id, start_time);
+    }
+
     // Create recordings directory if it doesn't exist
     let recordings_dir = project_root.join("recordings");
     println!("Creating recordings directory: {}", recordings_dir.display());
@@ -279,8 +428,10 @@ async fn start_recording(options: RecordingOptions) -> Result<RecordingSession,
         options.recording_mode.clone(),
         "--format".to_string(),
         options.format.clone(),
+        "--ffmpeg-path".to_string(),
+        ffmpeg_source.path(),
     ];
-    
+
     if let Some(duration) = options.duration {
         args.push("--duration".to_string());
         args.push(duration.to_string());
@@ -303,33 +454,53 @@ async fn start_recording(options: RecordingOptions) -> Result<RecordingSession,
     
     args.push("--wait-for-canvas".to_string());
     args.push(options.wait_for_canvas.to_string());
-    
+
+    let browser_engine = options.browser_engine.clone().unwrap_or_else(|| "chromium".to_string());
+    args.push("--browser".to_string());
+    args.push(browser_engine.clone());
+
+    if let Some(browser_channel) = options.browser_channel.clone() {
+        if !is_playwright_channel_installed(&browser_engine, &browser_channel) {
+            return Ok(RecordingSession {
+                id: session_id,
+                status: "failed".to_string(),
+                start_time,
+                duration: None,
+                output_path: None,
+                error: Some(format!(
+                    "Playwright channel '{}' is not installed. Run: pnpm exec playwright install {}",
+                    browser_channel, browser_channel
+                )),
+            });
+        }
+        args.push("--channel".to_string());
+        args.push(browser_channel);
+    }
+
+
     // Find pnpm executable
     let pnpm_path = find_command_path("pnpm")
         .ok_or("pnpm not found. Please install pnpm first.")?;
-    
-    // Start the recording process in the background
-    let mut cmd = std::process::Command::new(pnpm_path);
-    cmd.current_dir(project_root).args(&args);
-    
+
     println!("Running command: pnpm {} in directory: {}", args.join(" "), project_root.display());
-    
-    match cmd.spawn() {
-        Ok(child) => {
-            let pid = child.id();
-            println!("Started recording process with PID: {}", pid);
-            
-            // Store the child process for later termination
+
+    match runner::RecordingRunner::spawn(&pnpm_path, &args, project_root, sandbox::normalized_command_env()) {
+        Ok(runner) => {
+            println!("Started recording process with PID: {}", runner.pid());
+
+            let output_path_str = output_path.to_string_lossy().to_string();
+
+            // Store the runner for later termination and status polling
             if let Ok(mut recordings) = ACTIVE_RECORDINGS.lock() {
-                recordings.insert(session_id.clone(), child);
+                recordings.insert(session_id.clone(), runner);
             }
-            
+
             Ok(RecordingSession {
                 id: session_id,
                 status: "recording".to_string(),
                 start_time,
                 duration: None,
-                output_path: Some(output_path.to_string_lossy().to_string()),
+                output_path: Some(output_path_str),
                 error: None,
             })
         }
@@ -346,6 +517,106 @@ async fn start_recording(options: RecordingOptions) -> Result<RecordingSession,
     }
 }
 
+// Spawns the Playwright recorder with its stdout piped straight into an FFmpeg
+// encoder's stdin, publishing live to `options.rtmp_url` instead of writing a file.
+fn start_stream_recording(
+    ffmpeg_source: &ffmpeg::FfmpegSource,
+    options: &RecordingOptions,
+    project_root: &std::path::Path,
+    session_id: String,
+    start_time: String,
+) -> Result<RecordingSession, String> {
+    let endpoint = options.rtmp_url.clone()
+        .ok_or("Streaming mode requires an rtmp_url to publish to.")?;
+
+    let ffmpeg_path = ffmpeg_source.path();
+    let pnpm_path = find_command_path("pnpm")
+        .ok_or("pnpm not found. Please install pnpm first.")?;
+
+    let width = options.custom_width.unwrap_or(1280);
+    let height = options.custom_height.unwrap_or(720);
+    let frame_rate = options.frame_rate.unwrap_or(30);
+
+    // The recorder writes raw frames to stdout instead of a file under `recordings/`.
+    let mut recorder_cmd = Command::new(pnpm_path);
+    recorder_cmd
+        .current_dir(project_root)
+        .args(&[
+            "tsx", "src/cli.ts",
+            "--url", &options.figma_url,
+            "--mode", "stream",
+            "--width", &width.to_string(),
+            "--height", &height.to_string(),
+            "--frame-rate", &frame_rate.to_string(),
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    env::apply_normalized_env(&mut recorder_cmd);
+    let mut recorder = recorder_cmd.spawn()
+        .map_err(|e| format!("Failed to start recorder: {}", e))?;
+
+    let recorder_stdout = recorder.stdout.take()
+        .ok_or("Failed to capture recorder stdout")?;
+
+    // FFmpeg reads the raw frame stream on stdin and encodes directly to the endpoint.
+    let mut ffmpeg_cmd = Command::new(ffmpeg_path);
+    ffmpeg_cmd
+        .args(&[
+            "-f", "rawvideo",
+            "-pix_fmt", "rgba",
+            "-video_size", &format!("{}x{}", width, height),
+            "-framerate", &frame_rate.to_string(),
+            "-i", "-",
+            "-c:v", "libx264",
+            "-f", "flv",
+            &endpoint,
+        ])
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped());
+    env::apply_normalized_env(&mut ffmpeg_cmd);
+    let mut ffmpeg = ffmpeg_cmd.spawn()
+        .map_err(|e| format!("Failed to start ffmpeg encoder: {}", e))?;
+
+    let ffmpeg_stdin = ffmpeg.stdin.take()
+        .ok_or("Failed to capture ffmpeg stdin")?;
+    let ffmpeg_stderr = ffmpeg.stderr.take();
+
+    // Pump recorder stdout -> ffmpeg stdin on its own thread. `io::copy` blocks on
+    // the write side, so a slow encoder applies backpressure on frame intake instead
+    // of dropping frames or growing an unbounded buffer.
+    let pump_handle = std::thread::spawn(move || {
+        let mut recorder_stdout = recorder_stdout;
+        let mut ffmpeg_stdin = ffmpeg_stdin;
+        let _ = std::io::copy(&mut recorder_stdout, &mut ffmpeg_stdin);
+    });
+
+    let log: runner::LogRing = std::sync::Arc::new(Mutex::new(std::collections::VecDeque::new()));
+    let log_handle = Some(runner::spawn_log_pump(log.clone(), None, ffmpeg_stderr));
+
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.insert(session_id.clone(), ActiveStream {
+            recorder,
+            ffmpeg,
+            start_time: SystemTime::now(),
+            log,
+            log_handle,
+            endpoint: endpoint.clone(),
+            pump_handle: Some(pump_handle),
+            terminal: None,
+            terminal_since: None,
+        });
+    }
+
+    Ok(RecordingSession {
+        id: session_id,
+        status: "recording".to_string(),
+        start_time,
+        duration: None,
+        output_path: Some(endpoint),
+        error: None,
+    })
+}
+
 #[tauri::command]
 async fn open_recordings_folder() -> Result<(), String> {
     let project_path = get_project_root_path();
@@ -357,26 +628,46 @@ async fn open_recordings_folder() -> Result<(), String> {
 
     #[cfg(target_os = "macos")]
     {
-        Command::new("open")
-            .arg(&recordings_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+        let mut cmd = Command::new("open");
+        cmd.arg(&recordings_path);
+        env::apply_normalized_env(&mut cmd);
+        cmd.spawn().map_err(|e| format!("Failed to open folder: {}", e))?;
     }
 
     #[cfg(target_os = "windows")]
     {
-        Command::new("explorer")
-            .arg(&recordings_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+        let mut cmd = Command::new("explorer");
+        cmd.arg(&recordings_path);
+        env::apply_normalized_env(&mut cmd);
+        cmd.spawn().map_err(|e| format!("Failed to open folder: {}", e))?;
     }
 
     #[cfg(target_os = "linux")]
     {
-        Command::new("xdg-open")
-            .arg(&recordings_path)
-            .spawn()
-            .map_err(|e| format!("Failed to open folder: {}", e))?;
+        // Inside Flatpak, `xdg-open`/`gio` run sandboxed and may not reach the
+        // host's actual file manager; escape via `flatpak-spawn --host` first,
+        // the documented way for a Flatpak app to run a command against the host.
+        if sandbox::is_flatpak() {
+            let mut host_cmd = Command::new("flatpak-spawn");
+            host_cmd.args(&["--host", "xdg-open", &recordings_path]);
+            env::apply_normalized_env(&mut host_cmd);
+            if host_cmd.spawn().is_ok() {
+                return Ok(());
+            }
+        }
+
+        let mut cmd = Command::new("xdg-open");
+        cmd.arg(&recordings_path);
+        env::apply_normalized_env(&mut cmd);
+
+        if cmd.spawn().is_err() {
+            // Sandboxed/minimal desktops (notably inside Flatpak) often ship `gio`
+            // but not `xdg-open`; fall back to it before giving up.
+            let mut fallback = Command::new("gio");
+            fallback.args(&["open", &recordings_path]);
+            env::apply_normalized_env(&mut fallback);
+            fallback.spawn().map_err(|e| format!("Failed to open folder: {}", e))?;
+        }
     }
 
     Ok(())
@@ -419,55 +710,113 @@ async fn check_dependencies() -> Result<DependencyStatus, String> {
 #[tauri::command]
 async fn stop_recording(session_id: String) -> Result<(), String> {
     println!("Stopping recording session: {}", session_id);
-    
-    if let Ok(mut recordings) = ACTIVE_RECORDINGS.lock() {
-        if let Some(mut child) = recordings.remove(&session_id) {
-            match child.kill() {
-                Ok(()) => {
-                    println!("Successfully stopped recording session: {}", session_id);
-                    Ok(())
-                }
-                Err(e) => {
-                    Err(format!("Failed to stop recording process: {}", e))
+
+    // Take the session out of the map before the blocking graceful-shutdown
+    // wait (up to `GRACEFUL_SHUTDOWN_TIMEOUT`) and put it back afterwards, so
+    // the mutex is only ever held for quick map operations — otherwise every
+    // other session's `get_recording_status`/`start_recording` call would
+    // freeze behind this one's stop for up to 5 seconds.
+    let taken_runner = {
+        let mut recordings = ACTIVE_RECORDINGS.lock().map_err(|_| "Failed to access recording sessions".to_string())?;
+        recordings.remove(&session_id)
+    };
+
+    if let Some(mut runner) = taken_runner {
+        let result = runner.stop();
+        if let Ok(mut recordings) = ACTIVE_RECORDINGS.lock() {
+            recordings.insert(session_id.clone(), runner);
+        }
+        result?;
+        println!("Successfully stopped recording session: {}", session_id);
+        return Ok(());
+    }
+
+    let taken_stream = {
+        let mut streams = ACTIVE_STREAMS.lock().map_err(|_| "Failed to access recording sessions".to_string())?;
+        streams.remove(&session_id)
+    };
+    let mut stream = taken_stream.ok_or_else(|| format!("Recording session not found: {}", session_id))?;
+
+    if stream.terminal.is_none() {
+        // Stop feeding frames first: killing the recorder ends its stdout, which
+        // closes ffmpeg's stdin (once the pump thread's copy loop drains and drops
+        // it), letting the encoder finalize its output on EOF instead of being
+        // killed mid-write. Only force-kill ffmpeg if it doesn't exit on its own.
+        let _ = stream.recorder.kill();
+        let _ = stream.recorder.try_wait();
+        if let Some(handle) = stream.pump_handle.take() {
+            let _ = handle.join();
+        }
+
+        let exit_status = runner::wait_with_timeout(&mut stream.ffmpeg, runner::GRACEFUL_SHUTDOWN_TIMEOUT);
+        if let Some(handle) = stream.log_handle.take() {
+            let _ = handle.join();
+        }
+        match exit_status {
+            Ok(exit_status) => {
+                stream.terminal = Some((exit_status, elapsed_secs(stream.start_time)));
+                stream.terminal_since = Some(std::time::Instant::now());
+            }
+            Err(e) => {
+                if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+                    streams.insert(session_id.clone(), stream);
                 }
+                return Err(e);
             }
-        } else {
-            Err(format!("Recording session not found: {}", session_id))
         }
-    } else {
-        Err("Failed to access recording sessions".to_string())
     }
+
+    if let Ok(mut streams) = ACTIVE_STREAMS.lock() {
+        streams.insert(session_id.clone(), stream);
+    }
+
+    println!("Successfully stopped recording session: {}", session_id);
+    Ok(())
 }
 
 #[tauri::command]
-async fn get_recording_status(session_id: String) -> Result<RecordingSession, String> {
-    // Check if recording is still active
-    if let Ok(recordings) = ACTIVE_RECORDINGS.lock() {
-        if recordings.contains_key(&session_id) {
-            return Ok(RecordingSession {
-                id: session_id,
-                status: "recording".to_string(),
-                start_time: std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs()
-                    .to_string(),
-                duration: None,
-                output_path: None,
-                error: None,
-            });
+async fn get_recording_status(session_id: String) -> Result<runner::RecordingStatus, String> {
+    {
+        let mut recordings = ACTIVE_RECORDINGS.lock().map_err(|_| "Failed to access recording sessions".to_string())?;
+        if let Some(runner) = recordings.get_mut(&session_id) {
+            return runner.status();
         }
     }
-    
-    // If not in active recordings, assume it's completed or failed
-    Ok(RecordingSession {
-        id: session_id,
-        status: "completed".to_string(),
-        start_time: "".to_string(),
-        duration: None,
-        output_path: None,
-        error: None,
-    })
+
+    let mut streams = ACTIVE_STREAMS.lock().map_err(|_| "Failed to access recording sessions".to_string())?;
+    let stream = streams.get_mut(&session_id)
+        .ok_or_else(|| format!("Recording session not found: {}", session_id))?;
+
+    // Already reaped on an earlier poll; keep returning the cached terminal result.
+    if let Some((exit_status, duration_secs)) = stream.terminal {
+        return Ok(runner::classify_exit(exit_status, duration_secs, &stream.log));
+    }
+
+    // The encoder is the authoritative half of the pipe: if it has exited, the
+    // stream is over even if the recorder side is still winding down.
+    match stream.ffmpeg.try_wait() {
+        Ok(None) => Ok(runner::RecordingStatus::Recording {
+            pid: stream.ffmpeg.id(),
+            elapsed_secs: elapsed_secs(stream.start_time),
+            last_log_lines: runner::log_tail(&stream.log),
+        }),
+        Ok(Some(exit_status)) => {
+            let _ = stream.recorder.kill();
+            let _ = stream.recorder.try_wait();
+            if let Some(handle) = stream.pump_handle.take() {
+                let _ = handle.join();
+            }
+            if let Some(handle) = stream.log_handle.take() {
+                let _ = handle.join();
+            }
+
+            let duration_secs = elapsed_secs(stream.start_time);
+            stream.terminal = Some((exit_status, duration_secs));
+            stream.terminal_since = Some(std::time::Instant::now());
+            Ok(runner::classify_exit(exit_status, duration_secs, &stream.log))
+        }
+        Err(e) => Err(format!("Failed to check stream status: {}", e)),
+    }
 }
 
 #[tauri::command]
@@ -490,6 +839,195 @@ async fn list_recordings() -> Result<Vec<String>, String> {
     Ok(recordings)
 }
 
+// A full environment report the user can copy into a bug report, analogous to a
+// CLI `info` subcommand: every booleans in `check_dependencies` plus the absolute
+// paths/versions actually resolved and the PATH used to spawn child processes.
+#[tauri::command]
+async fn diagnostics(app: tauri::AppHandle) -> Result<DiagnosticsReport, String> {
+    let mut items = Vec::new();
+
+    // (display name, command, args, minimum version, install hint)
+    let tool_checks: Vec<(&str, &str, Vec<&str>, Option<(u64, u64, u64)>, Option<String>)> = vec![
+        ("Node.js", "node", vec!["--version"], Some((18, 0, 0)), None),
+        ("pnpm", "pnpm", vec!["--version"], Some((8, 0, 0)), Some(get_pnpm_install_command())),
+    ];
+
+    for (name, command, args, min_version, install_hint) in tool_checks {
+        items.push(inspect_dependency(name, command, &args, min_version, install_hint));
+    }
+
+    // FFmpeg goes through the same bundled-sidecar-first resolution as
+    // `start_recording`/`check_system_dependencies`, rather than a plain PATH
+    // lookup, so a build that bundles its own FFmpeg reports it as available.
+    let ffmpeg_source = ffmpeg::resolve_ffmpeg(&app);
+    items.push(match &ffmpeg_source {
+        ffmpeg::FfmpegSource::Bundled(path) => DependencyReport {
+            name: "FFmpeg".to_string(),
+            path: Some(path.to_string_lossy().to_string()),
+            install_source: Some(ffmpeg_source.label().to_string()),
+            version: Some(ffmpeg_source.label().to_string()),
+            min_version: Some("4.0.0".to_string()),
+            status: "pass".to_string(),
+            hint: None,
+        },
+        ffmpeg::FfmpegSource::System(_) => {
+            inspect_dependency("FFmpeg", "ffmpeg", &["-version"], Some((4, 0, 0)), get_ffmpeg_install_command())
+        }
+    });
+
+    let (playwright_installed, playwright_version) = check_playwright_browsers();
+    let playwright_revisions = playwright_cache_entries();
+    items.push(DependencyReport {
+        name: "Playwright Browsers".to_string(),
+        path: Some(playwright_cache_dir()),
+        install_source: None,
+        version: if playwright_revisions.is_empty() {
+            playwright_version
+        } else {
+            Some(playwright_revisions.join(", "))
+        },
+        min_version: None,
+        status: if playwright_installed { "pass".to_string() } else { "warn".to_string() },
+        hint: if playwright_installed {
+            None
+        } else {
+            Some("pnpm exec playwright install".to_string())
+        },
+    });
+
+    let project_root = get_project_root_path();
+    let project_version = read_package_json_version(&project_root);
+    items.push(DependencyReport {
+        name: "package.json".to_string(),
+        path: Some(format!("{}/package.json", project_root)),
+        install_source: None,
+        version: project_version.clone(),
+        min_version: None,
+        status: if project_version.is_some() { "pass".to_string() } else { "warn".to_string() },
+        hint: None,
+    });
+
+    Ok(DiagnosticsReport {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        project_root,
+        project_version,
+        normalized_path: env::normalized_path(),
+        playwright_cache_dir: playwright_cache_dir(),
+        items,
+    })
+}
+
+/// Builds a single tool's [`DependencyReport`]: resolved path, inferred install
+/// source, a real parsed semver (not just the raw first line of output), and a
+/// pass/warn/fail verdict against `min_version`.
+fn inspect_dependency(
+    name: &str,
+    command: &str,
+    args: &[&str],
+    min_version: Option<(u64, u64, u64)>,
+    install_hint: Option<String>,
+) -> DependencyReport {
+    let path = find_command_path(command);
+    let (installed, version_text) = check_command(command, args);
+    let install_source = path.as_deref().map(infer_install_source);
+    let parsed_version = version_text.as_deref().and_then(extract_semver);
+
+    let status = if !installed {
+        "fail".to_string()
+    } else {
+        match (parsed_version, min_version) {
+            (Some(found), Some(min)) if found < min => "warn".to_string(),
+            // Installed but we couldn't parse a version (e.g. empty "installed"
+            // placeholder) — can't confirm it meets the minimum, so warn rather
+            // than silently passing or failing outright.
+            (None, Some(_)) => "warn".to_string(),
+            _ => "pass".to_string(),
+        }
+    };
+
+    DependencyReport {
+        name: name.to_string(),
+        path,
+        install_source,
+        version: parsed_version
+            .map(|(major, minor, patch)| format!("{}.{}.{}", major, minor, patch))
+            .or(version_text),
+        min_version: min_version.map(|(major, minor, patch)| format!("{}.{}.{}", major, minor, patch)),
+        status,
+        hint: if installed { None } else { install_hint },
+    }
+}
+
+/// Extracts the first `x.y.z` token from version command output, e.g. the `6.1.1`
+/// in `ffmpeg version 6.1.1-full_build ...` or the `20.11.0` in `v20.11.0`.
+fn extract_semver(text: &str) -> Option<(u64, u64, u64)> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i].is_ascii_digit() {
+            let start = i;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'.') {
+                i += 1;
+            }
+            let candidate = &text[start..i];
+            let parts: Vec<&str> = candidate.split('.').collect();
+            if parts.len() >= 3 {
+                if let (Ok(major), Ok(minor), Ok(patch)) =
+                    (parts[0].parse::<u64>(), parts[1].parse::<u64>(), parts[2].parse::<u64>())
+                {
+                    return Some((major, minor, patch));
+                }
+            }
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Infers where a resolved binary was installed from based on its path, mirroring
+/// the well-known locations `get_command_search_paths` already probes.
+fn infer_install_source(path: &str) -> String {
+    let lower = path.to_lowercase();
+    if path.contains("/opt/homebrew") {
+        "Homebrew (Apple Silicon)".to_string()
+    } else if path.contains("/usr/local/Cellar") || path.contains("/usr/local/bin") || path.contains("/usr/local/opt") {
+        "Homebrew (Intel)".to_string()
+    } else if path.contains("/opt/local") {
+        "MacPorts".to_string()
+    } else if path.contains("/.nvm/") {
+        "nvm".to_string()
+    } else if path.contains("/.volta/") {
+        "Volta".to_string()
+    } else if path.contains("/snap/") {
+        "Snap".to_string()
+    } else if path.contains("flatpak") {
+        "Flatpak".to_string()
+    } else if lower.contains("chocolatey") {
+        "Chocolatey".to_string()
+    } else if path.starts_with("/usr/bin") || path.starts_with("/bin") || lower.starts_with("c:\\windows") {
+        "System".to_string()
+    } else {
+        "Unknown".to_string()
+    }
+}
+
+/// Pulls `"version"` out of `<project_root>/package.json` with a plain string scan,
+/// matching how the rest of this file avoids pulling in a JSON crate for one field.
+fn read_package_json_version(project_root: &str) -> Option<String> {
+    let contents = std::fs::read_to_string(format!("{}/package.json", project_root)).ok()?;
+    let key_pos = contents.find("\"version\"")?;
+    let after_key = &contents[key_pos + "\"version\"".len()..];
+    let colon_pos = after_key.find(':')?;
+    let after_colon = &after_key[colon_pos + 1..];
+    let start_quote = after_colon.find('"')?;
+    let rest = &after_colon[start_quote + 1..];
+    let end_quote = rest.find('"')?;
+    Some(rest[..end_quote].to_string())
+}
+
 fn check_playwright_browsers() -> (bool, Option<String>) {
     // First try using detected pnpm path
     if let Some(pnpm_path) = find_command_path("pnpm") {
@@ -517,31 +1055,151 @@ fn check_playwright_browsers() -> (bool, Option<String>) {
     
     // Try checking if browsers are installed by looking for browser directories
     // Playwright typically installs browsers in different locations per OS
-    let home_dir = env::var("HOME").unwrap_or_default();
-    
+    if !playwright_cache_entries().is_empty() {
+        return (true, Some("browsers installed".to_string()));
+    }
+
+    (false, None)
+}
+
+fn playwright_cache_dir() -> String {
+    let home_dir = std::env::var("HOME").unwrap_or_default();
+
     #[cfg(target_os = "macos")]
-    let playwright_cache = format!("{}/Library/Caches/ms-playwright", home_dir);
-    
+    return format!("{}/Library/Caches/ms-playwright", home_dir);
+
     #[cfg(target_os = "windows")]
-    let playwright_cache = if let Ok(appdata) = env::var("LOCALAPPDATA") {
-        format!("{}\\ms-playwright", appdata)
-    } else if let Ok(userprofile) = env::var("USERPROFILE") {
-        format!("{}\\AppData\\Local\\ms-playwright", userprofile)
-    } else {
+    {
+        if let Ok(appdata) = std::env::var("LOCALAPPDATA") {
+            return format!("{}\\ms-playwright", appdata);
+        }
+        if let Ok(userprofile) = std::env::var("USERPROFILE") {
+            return format!("{}\\AppData\\Local\\ms-playwright", userprofile);
+        }
         format!("{}\\ms-playwright", home_dir)
-    };
-    
+    }
+
     #[cfg(target_os = "linux")]
-    let playwright_cache = format!("{}/.cache/ms-playwright", home_dir);
-    
-    if let Ok(entries) = std::fs::read_dir(&playwright_cache) {
-        let browser_count = entries.filter_map(|entry| entry.ok()).count();
-        if browser_count > 0 {
-            return (true, Some("browsers installed".to_string()));
+    return format!("{}/.cache/ms-playwright", home_dir);
+}
+
+/// Revision folder names directly under the ms-playwright cache, e.g. "chromium-1097".
+fn playwright_cache_entries() -> Vec<String> {
+    std::fs::read_dir(playwright_cache_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Channels Playwright drives through an already-installed system browser rather
+/// than downloading into its own cache; these never get a ms-playwright revision
+/// folder, so a cache-folder check can never find them.
+const SYSTEM_BROWSER_CHANNELS: &[&str] = &[
+    "chrome", "chrome-beta", "chrome-dev", "chrome-canary",
+    "msedge", "msedge-beta", "msedge-dev", "msedge-canary",
+];
+
+/// Verifies that the requested browser engine/channel is actually usable.
+/// System-browser channels (Chrome/Edge and their beta/dev/canary variants) are
+/// resolved by locating the real system install, exactly like Playwright itself
+/// does for them — it never puts them in its own cache. WebKit Technology
+/// Preview gets its own revision directory, distinguished with a completion
+/// marker. Anything else falls back to the ms-playwright cache revision check.
+fn is_playwright_channel_installed(engine: &str, channel: &str) -> bool {
+    if SYSTEM_BROWSER_CHANNELS.contains(&channel) {
+        return system_browser_channel_installed(channel);
+    }
+
+    if channel == "webkit-technology-preview" {
+        return webkit_technology_preview_installed();
+    }
+
+    let channel_key = channel.replace('-', "_").to_lowercase();
+    playwright_cache_entries()
+        .iter()
+        .any(|name| name.starts_with(engine) && name.to_lowercase().contains(&channel_key))
+}
+
+/// Looks for the actual system browser binary backing a Playwright channel.
+fn system_browser_channel_installed(channel: &str) -> bool {
+    system_browser_search_paths(channel)
+        .iter()
+        .any(|path| std::fs::metadata(path).is_ok())
+}
+
+fn system_browser_search_paths(channel: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+
+    #[cfg(target_os = "macos")]
+    {
+        let app_name = match channel {
+            "chrome" => "Google Chrome",
+            "chrome-beta" => "Google Chrome Beta",
+            "chrome-dev" => "Google Chrome Dev",
+            "chrome-canary" => "Google Chrome Canary",
+            "msedge" => "Microsoft Edge",
+            "msedge-beta" => "Microsoft Edge Beta",
+            "msedge-dev" => "Microsoft Edge Dev",
+            "msedge-canary" => "Microsoft Edge Canary",
+            _ => return paths,
+        };
+        paths.push(format!("/Applications/{}.app/Contents/MacOS/{}", app_name, app_name));
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let binary = match channel {
+            "chrome" => "google-chrome-stable",
+            "chrome-beta" => "google-chrome-beta",
+            "chrome-dev" => "google-chrome-unstable",
+            "chrome-canary" => "google-chrome-canary",
+            "msedge" => "microsoft-edge-stable",
+            "msedge-beta" => "microsoft-edge-beta",
+            "msedge-dev" => "microsoft-edge-dev",
+            _ => return paths,
+        };
+        paths.push(format!("/usr/bin/{}", binary));
+        paths.push(format!("/opt/{}/{}", binary, binary));
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let is_edge = channel.starts_with("msedge");
+        let (vendor_dir, exe) = if is_edge {
+            ("Microsoft\\Edge", "msedge.exe")
+        } else {
+            ("Google\\Chrome", "chrome.exe")
+        };
+        if let Ok(program_files) = std::env::var("PROGRAMFILES") {
+            paths.push(format!("{}\\{}\\Application\\{}", program_files, vendor_dir, exe));
+        }
+        if let Ok(program_files_x86) = std::env::var("PROGRAMFILES(X86)") {
+            paths.push(format!("{}\\{}\\Application\\{}", program_files_x86, vendor_dir, exe));
         }
     }
-    
-    (false, None)
+
+    paths
+}
+
+/// WebKit Technology Preview isn't layered inside the regular WebKit revision
+/// folder — Playwright gives it its own, and (like every Playwright-managed
+/// browser) only marks it usable once it writes an `INSTALLATION_COMPLETE`
+/// marker file into that folder.
+fn webkit_technology_preview_installed() -> bool {
+    playwright_cache_entries().iter().any(|name| {
+        let lower = name.to_lowercase();
+        if !lower.starts_with("webkit") || !lower.contains("technology") {
+            return false;
+        }
+        std::path::Path::new(&playwright_cache_dir())
+            .join(name)
+            .join("INSTALLATION_COMPLETE")
+            .exists()
+    })
 }
 
 fn check_command(command: &str, args: &[&str]) -> (bool, Option<String>) {
@@ -592,16 +1250,15 @@ fn check_command_with_shell(command: &str, args: &[&str]) -> (bool, Option<Strin
     );
 
     let output = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", &shell_cmd])
-            .env("PATH", std::env::var("PATH").unwrap_or_default())
-            .output()
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", &shell_cmd]);
+        env::apply_normalized_env(&mut cmd);
+        cmd.output()
     } else {
-        Command::new("sh")
-            .arg("-c")
-            .arg(&shell_cmd)
-            .env("PATH", std::env::var("PATH").unwrap_or_default())
-            .output()
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(&shell_cmd);
+        env::apply_normalized_env(&mut cmd);
+        cmd.output()
     };
 
     if let Ok(output) = output {
@@ -674,32 +1331,16 @@ fn check_ffmpeg_specific() -> (bool, Option<String>) {
     #[cfg(target_os = "macos")]
     {
         println!("Trying macOS-specific FFmpeg detection...");
-        // On macOS, try with enhanced PATH including common Homebrew locations
-        let extra_brew_paths = vec![
-            "/opt/homebrew/bin", 
-            "/usr/local/bin",
-            "/opt/homebrew/opt/ffmpeg/bin"
-        ];
-        
-        // Get existing PATH or empty string
-        let mut path_env = std::env::var("PATH").unwrap_or_default();
-        println!("Current PATH length: {}", path_env.len());
-        
-        // Add brew paths
-        for brew_path in extra_brew_paths {
-            if !path_env.contains(brew_path) {
-                path_env = format!("{}:{}",brew_path, path_env);
-                println!("Added to PATH: {}", brew_path);
-            }
-        }
-        
-        // Try with enhanced PATH
+        // On macOS, try with the normalized PATH, which already puts both
+        // Homebrew bin directories (Apple Silicon's first) ahead of the rest.
+        println!("Normalized PATH: {}", env::normalized_path());
+
         let shell_cmd = "ffmpeg -version";
-        let output = Command::new("sh")
-            .args(&["-c", shell_cmd])
-            .env("PATH", path_env)
-            .output();
-            
+        let mut cmd = Command::new("sh");
+        cmd.args(&["-c", shell_cmd]);
+        env::apply_normalized_env(&mut cmd);
+        let output = cmd.output();
+
         if let Ok(output) = output {
             if output.status.success() {
                 let output_str = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -732,15 +1373,15 @@ fn find_command_path(command: &str) -> Option<String> {
     };
 
     let shell_result = if cfg!(target_os = "windows") {
-        Command::new("cmd")
-            .args(&["/C", &shell_command])
-            .env("PATH", std::env::var("PATH").unwrap_or_default())
-            .output()
+        let mut cmd = Command::new("cmd");
+        cmd.args(&["/C", &shell_command]);
+        env::apply_normalized_env(&mut cmd);
+        cmd.output()
     } else {
-        Command::new("sh")
-            .args(&["-c", &shell_command])
-            .env("PATH", std::env::var("PATH").unwrap_or_default())
-            .output()
+        let mut cmd = Command::new("sh");
+        cmd.args(&["-c", &shell_command]);
+        env::apply_normalized_env(&mut cmd);
+        cmd.output()
     };
 
     if let Ok(output) = shell_result {
@@ -785,7 +1426,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
     let mut paths = Vec::new();
     
     // Get PATH environment variable and split properly for the platform
-    if let Ok(path_env) = env::var("PATH") {
+    if let Ok(path_env) = std::env::var("PATH") {
         #[cfg(target_os = "windows")]
         let path_separator = ';';
         #[cfg(not(target_os = "windows"))]
@@ -811,7 +1452,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
         paths.extend(macos_paths);
 
         // Check user's home directory for npm global packages
-        if let Ok(home) = env::var("HOME") {
+        if let Ok(home) = std::env::var("HOME") {
             paths.push(format!("{}/.npm-global/bin/{}", home, command));
             paths.push(format!("{}/bin/{}", home, command));
             // Check for nvm installations
@@ -829,7 +1470,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
                     "/Applications/Node.js/bin/node".to_string(),
                 ]);
                 // Check for nvm versions
-                if let Ok(home) = env::var("HOME") {
+                if let Ok(home) = std::env::var("HOME") {
                     if let Ok(entries) = std::fs::read_dir(format!("{}/.nvm/versions/node", home)) {
                         for entry in entries.flatten() {
                             paths.push(format!("{}/bin/node", entry.path().display()));
@@ -850,7 +1491,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
                 ]);
                 
                 // Also check for Cellar installations dynamically
-                if let Ok(home) = env::var("HOME") {
+                if let Ok(home) = std::env::var("HOME") {
                     // Check both Intel and Apple Silicon Homebrew Cellar paths
                     let cellar_paths = vec![
                         "/usr/local/Cellar/ffmpeg",
@@ -877,7 +1518,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
                 }
             }
             "pnpm" => {
-                if let Ok(home) = env::var("HOME") {
+                if let Ok(home) = std::env::var("HOME") {
                     paths.push(format!("{}/.local/share/pnpm/pnpm", home));
                     paths.push(format!("{}/Library/pnpm/pnpm", home));
                     // Check for global npm installation
@@ -905,12 +1546,12 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
         paths.extend(windows_paths);
 
         // Check user's AppData for npm global packages
-        if let Ok(appdata) = env::var("APPDATA") {
+        if let Ok(appdata) = std::env::var("APPDATA") {
             paths.push(format!("{}\\npm\\{}.cmd", appdata, command));
             paths.push(format!("{}\\npm\\{}.exe", appdata, command));
         }
 
-        if let Ok(userprofile) = env::var("USERPROFILE") {
+        if let Ok(userprofile) = std::env::var("USERPROFILE") {
             paths.push(format!("{}\\AppData\\Roaming\\npm\\{}.cmd", userprofile, command));
             paths.push(format!("{}\\AppData\\Roaming\\npm\\{}.exe", userprofile, command));
         }
@@ -925,12 +1566,12 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
                 ]);
                 
                 // Check chocolatey installation
-                if let Ok(programdata) = env::var("ProgramData") {
+                if let Ok(programdata) = std::env::var("ProgramData") {
                     paths.push(format!("{}\\chocolatey\\bin\\ffmpeg.exe", programdata));
                 }
             }
             "pnpm" => {
-                if let Ok(appdata) = env::var("APPDATA") {
+                if let Ok(appdata) = std::env::var("APPDATA") {
                     paths.push(format!("{}\\npm\\pnpm.cmd", appdata));
                 }
             }
@@ -951,7 +1592,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
         paths.extend(linux_paths);
 
         // Check user's home directory
-        if let Ok(home) = env::var("HOME") {
+        if let Ok(home) = std::env::var("HOME") {
             paths.push(format!("{}/.local/bin/{}", home, command));
             paths.push(format!("{}/bin/{}", home, command));
             paths.push(format!("{}/.npm-global/bin/{}", home, command));
@@ -963,7 +1604,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
         match command {
             "node" => {
                 // Check for nvm versions
-                if let Ok(home) = env::var("HOME") {
+                if let Ok(home) = std::env::var("HOME") {
                     if let Ok(entries) = std::fs::read_dir(format!("{}/.nvm/versions/node", home)) {
                         for entry in entries.flatten() {
                             paths.push(format!("{}/bin/node", entry.path().display()));
@@ -972,7 +1613,7 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
                 }
             }
             "pnpm" => {
-                if let Ok(home) = env::var("HOME") {
+                if let Ok(home) = std::env::var("HOME") {
                     paths.push(format!("{}/.local/share/pnpm/pnpm", home));
                 }
             }
@@ -993,26 +1634,29 @@ fn get_command_search_paths(command: &str) -> Vec<String> {
 
 fn get_ffmpeg_install_command() -> Option<String> {
     #[cfg(target_os = "macos")]
-    return Some("brew install ffmpeg".to_string());
-    
+    return Some(match BrewVariant::detect() {
+        Some(variant) => format!("{} install ffmpeg", variant.brew_binary()),
+        None => "brew install ffmpeg".to_string(),
+    });
+
     #[cfg(target_os = "windows")]
     return Some("choco install ffmpeg".to_string());
-    
+
     #[cfg(target_os = "linux")]
     return Some("sudo apt install ffmpeg".to_string());
-    
+
     #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
     return None;
 }
 
 fn get_project_root_path() -> String {
     // Get the directory containing the Tauri app, then go up to find the project root
-    let exe_path = env::current_exe().unwrap_or_else(|_| PathBuf::from("."));
+    let exe_path = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("."));
     let _current_dir = exe_path.parent().unwrap_or_else(|| std::path::Path::new("."));
     
     // During development, we're in gui/src-tauri/target/debug
     // During production, we need to find the project root differently
-    if let Ok(cwd) = env::current_dir() {
+    if let Ok(cwd) = std::env::current_dir() {
         if cwd.join("src").exists() && cwd.join("package.json").exists() {
             // We're in the project root
             return cwd.to_string_lossy().to_string();
@@ -1023,7 +1667,7 @@ fn get_project_root_path() -> String {
     }
     
     // Fallback to current directory
-    env::current_dir()
+    std::env::current_dir()
         .unwrap_or_else(|_| PathBuf::from("."))
         .to_string_lossy()
         .to_string()
@@ -1031,7 +1675,7 @@ fn get_project_root_path() -> String {
 
 fn get_main_project_path() -> String {
     // This function gets the main project path (not the GUI subfolder)
-    if let Ok(cwd) = env::current_dir() {
+    if let Ok(cwd) = std::env::current_dir() {
         // If we're in gui/src-tauri, go up two levels
         if cwd.to_string_lossy().contains("gui/src-tauri") {
             if let Some(parent) = cwd.parent().and_then(|p| p.parent()) {
@@ -1062,13 +1706,15 @@ pub fn run() {
             greet,
             check_system_dependencies,
             install_dependencies,
+            install_ffmpeg,
             install_playwright_browsers,
             start_recording,
             open_recordings_folder,
             check_dependencies,
             stop_recording,
             get_recording_status,
-            list_recordings
+            list_recordings,
+            diagnostics
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,242 @@
+// A process-lifecycle abstraction for the spawned recorder, modeled on mozrunner's
+// `Runner`/`RunnerProcess` split: a `RecordingRunner` owns the child, continuously
+// drains its stdout+stderr into a bounded ring buffer instead of reading it once at
+// exit, and exposes non-blocking status polling plus a graceful-then-forced stop,
+// so a crash mid-recording surfaces real diagnostics instead of a blank failure.
+
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStderr, ChildStdout, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+const LOG_RING_CAPACITY: usize = 200;
+pub const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a terminal session is kept around after exiting, so the frontend has
+/// time to poll the final status at least once before it's evicted.
+pub const SESSION_RETENTION: Duration = Duration::from_secs(5 * 60);
+
+pub type LogRing = Arc<Mutex<VecDeque<String>>>;
+
+/// A snapshot of a recording/encoding process's lifecycle state for the frontend
+/// to poll, in place of a flat status string.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "state")]
+pub enum RecordingStatus {
+    Starting,
+    Recording {
+        pid: u32,
+        elapsed_secs: f64,
+        last_log_lines: Vec<String>,
+    },
+    Exited {
+        code: Option<i32>,
+        duration_secs: f64,
+    },
+    Crashed {
+        code: Option<i32>,
+        duration_secs: f64,
+        tail: Vec<String>,
+    },
+}
+
+/// Owns a spawned recorder/encoder child: explicit args/env, redirected
+/// stdout+stderr drained continuously into a ring buffer, and the bookkeeping to
+/// report a rich status and shut it down gracefully exactly once.
+pub struct RecordingRunner {
+    child: Child,
+    start_time: std::time::SystemTime,
+    log: LogRing,
+    log_handle: Option<std::thread::JoinHandle<()>>,
+    // The exit status plus the duration computed once, at the moment the
+    // process was first reaped — not recomputed from `start_time` on every
+    // poll, which would keep growing for as long as the frontend keeps asking.
+    terminal: Option<(ExitStatus, f64)>,
+    terminal_since: Option<Instant>,
+}
+
+impl RecordingRunner {
+    /// Spawns `command` with `args` in `cwd` under the given environment, with
+    /// stdin/stdout/stderr piped so frontend-facing status can report live output.
+    pub fn spawn(
+        command: &str,
+        args: &[String],
+        cwd: &std::path::Path,
+        env: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<RecordingRunner, String> {
+        let mut cmd = Command::new(command);
+        cmd.current_dir(cwd)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .env_clear()
+            .envs(env);
+
+        let mut child = cmd
+            .spawn()
+            .map_err(|e| format!("Failed to start process: {}", e))?;
+
+        let log: LogRing = Arc::new(Mutex::new(VecDeque::with_capacity(LOG_RING_CAPACITY)));
+        let log_handle = Some(spawn_log_pump(log.clone(), child.stdout.take(), child.stderr.take()));
+
+        Ok(RecordingRunner {
+            child,
+            start_time: std::time::SystemTime::now(),
+            log,
+            log_handle,
+            terminal: None,
+            terminal_since: None,
+        })
+    }
+
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    pub fn elapsed_secs(&self) -> f64 {
+        crate::elapsed_secs(self.start_time)
+    }
+
+    fn join_log_pump(&mut self) {
+        if let Some(handle) = self.log_handle.take() {
+            let _ = handle.join();
+        }
+    }
+
+    /// Non-blocking poll of the child's lifecycle state; never blocks the calling
+    /// (Tauri command) thread. Reaps the process exactly once, caching the exit
+    /// status so repeated polls after exit keep returning the same status.
+    pub fn status(&mut self) -> Result<RecordingStatus, String> {
+        if let Some((exit_status, duration_secs)) = self.terminal {
+            return Ok(classify_exit(exit_status, duration_secs, &self.log));
+        }
+
+        match self.child.try_wait() {
+            Ok(None) => Ok(RecordingStatus::Recording {
+                pid: self.pid(),
+                elapsed_secs: self.elapsed_secs(),
+                last_log_lines: log_tail(&self.log),
+            }),
+            Ok(Some(exit_status)) => {
+                self.join_log_pump();
+                let duration_secs = self.elapsed_secs();
+                self.terminal = Some((exit_status, duration_secs));
+                self.terminal_since = Some(Instant::now());
+                Ok(classify_exit(exit_status, duration_secs, &self.log))
+            }
+            Err(e) => Err(format!("Failed to check process status: {}", e)),
+        }
+    }
+
+    /// Whether this session reached a terminal status more than
+    /// [`SESSION_RETENTION`] ago, and can be evicted from the session map.
+    pub fn is_stale(&self) -> bool {
+        self.terminal_since.is_some_and(|since| since.elapsed() >= SESSION_RETENTION)
+    }
+
+    /// Stops the process. This child is the `pnpm tsx src/cli.ts ...` recorder
+    /// wrapper, not ffmpeg directly, so there's no documented stdin "quit
+    /// cleanly" keystroke to send it. On Unix it gets SIGTERM first and up to
+    /// [`GRACEFUL_SHUTDOWN_TIMEOUT`] to exit on its own before being force-killed
+    /// and reaped; elsewhere it is killed immediately. A no-op if the process
+    /// has already exited.
+    pub fn stop(&mut self) -> Result<(), String> {
+        if self.terminal.is_some() {
+            return Ok(());
+        }
+
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-TERM", &self.child.id().to_string()])
+            .status();
+        #[cfg(not(unix))]
+        self.child.kill().map_err(|e| format!("Failed to stop process: {}", e))?;
+
+        let exit_status = wait_with_timeout(&mut self.child, GRACEFUL_SHUTDOWN_TIMEOUT)?;
+        self.join_log_pump();
+        self.terminal = Some((exit_status, self.elapsed_secs()));
+        self.terminal_since = Some(Instant::now());
+        Ok(())
+    }
+}
+
+/// Snapshot of a [`LogRing`]'s current contents, oldest first.
+pub fn log_tail(log: &LogRing) -> Vec<String> {
+    log.lock().map(|log| log.iter().cloned().collect()).unwrap_or_default()
+}
+
+/// Turns a reaped [`ExitStatus`] plus its process's log ring into the matching
+/// terminal [`RecordingStatus`]. `duration_secs` must be computed once, at the
+/// moment the process was first reaped, and reused on every later call — not
+/// recomputed from `start_time`, which would keep growing on repeated polls.
+/// Shared by [`RecordingRunner`] and the live-stream encoder, which owns its
+/// child and log ring directly.
+pub fn classify_exit(exit_status: ExitStatus, duration_secs: f64, log: &LogRing) -> RecordingStatus {
+    if exit_status.success() {
+        RecordingStatus::Exited { code: exit_status.code(), duration_secs }
+    } else {
+        RecordingStatus::Crashed {
+            code: exit_status.code(),
+            duration_secs,
+            tail: log_tail(log),
+        }
+    }
+}
+
+/// Polls `child` for up to `timeout`, force-killing and reaping it if it hasn't
+/// exited on its own by the deadline. Shared by [`RecordingRunner::stop`] and the
+/// live-stream encoder, which owns its child directly rather than through a
+/// `RecordingRunner`.
+pub fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<ExitStatus, String> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match child.try_wait() {
+            Ok(Some(exit_status)) => return Ok(exit_status),
+            Ok(None) if Instant::now() >= deadline => {
+                child.kill().map_err(|e| format!("Failed to force-stop process: {}", e))?;
+                return child.wait().map_err(|e| format!("Failed to reap process: {}", e));
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(100)),
+            Err(e) => return Err(format!("Failed to check process status: {}", e)),
+        }
+    }
+}
+
+/// Starts a background thread draining `stdout`/`stderr` line-by-line into `log`
+/// concurrently (each on its own inner thread) so neither side backs up behind
+/// the other, returning a single handle that joins both.
+pub fn spawn_log_pump(
+    log: LogRing,
+    stdout: Option<ChildStdout>,
+    stderr: Option<ChildStderr>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let stdout_log = log.clone();
+        let stdout_thread = stdout.map(|pipe| std::thread::spawn(move || drain_into(pipe, stdout_log)));
+        let stderr_thread = stderr.map(|pipe| std::thread::spawn(move || drain_into(pipe, log)));
+
+        if let Some(handle) = stdout_thread {
+            let _ = handle.join();
+        }
+        if let Some(handle) = stderr_thread {
+            let _ = handle.join();
+        }
+    })
+}
+
+fn drain_into(pipe: impl std::io::Read, log: LogRing) {
+    let reader = BufReader::new(pipe);
+    for line in reader.lines().map_while(Result::ok) {
+        if let Ok(mut log) = log.lock() {
+            if log.len() == LOG_RING_CAPACITY {
+                log.pop_front();
+            }
+            log.push_back(line);
+        }
+    }
+}
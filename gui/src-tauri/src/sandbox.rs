@@ -0,0 +1,79 @@
+// Detects whether this app is running inside a Linux AppImage/Flatpak/Snap bundle,
+// and builds a clean spawn environment for those cases. Bundled Linux packaging
+// injects its own `LD_LIBRARY_PATH`/`GST_PLUGIN_*`/rewritten `PATH` into the
+// process, which leaks into children like ffmpeg/node and either hides the
+// system's real binaries or makes them crash against the bundle's libraries.
+
+use std::collections::HashMap;
+
+/// True when running inside an AppImage.
+pub fn is_appimage() -> bool {
+    std::env::var_os("APPIMAGE").is_some() || std::env::var_os("APPDIR").is_some()
+}
+
+/// True when running inside a Flatpak sandbox.
+pub fn is_flatpak() -> bool {
+    std::env::var_os("FLATPAK_ID").is_some() || std::path::Path::new("/.flatpak-info").exists()
+}
+
+/// True when running inside a Snap sandbox.
+pub fn is_snap() -> bool {
+    std::env::var_os("SNAP").is_some() || std::env::var_os("SNAP_NAME").is_some()
+}
+
+/// True when any of the above packaging/sandboxing mechanisms is detected.
+pub fn is_sandboxed() -> bool {
+    is_appimage() || is_flatpak() || is_snap()
+}
+
+// Variables a Linux bundler injects for its own bundled libraries; letting these
+// leak into a spawned system tool can make it load the wrong shared libraries.
+const BUNDLE_INJECTED_VARS: &[&str] = &[
+    "LD_LIBRARY_PATH",
+    "GST_PLUGIN_PATH",
+    "GST_PLUGIN_SYSTEM_PATH",
+    "GIO_MODULE_DIR",
+    "GDK_PIXBUF_MODULE_FILE",
+];
+
+/// Directories a Linux bundler injects its own binaries into (`$APPDIR/usr/bin`
+/// for AppImage, `/app/bin` etc. for Flatpak, `$SNAP` for Snap). `PATH` entries
+/// under these roots are stripped by `env::normalized_path()` so a same-named
+/// binary shipped inside the bundle never shadows the system's real one.
+pub fn bundle_path_roots() -> Vec<String> {
+    let mut roots = Vec::new();
+
+    if let Some(appdir) = std::env::var_os("APPDIR") {
+        roots.push(appdir.to_string_lossy().to_string());
+    }
+    if is_flatpak() {
+        roots.push("/app".to_string());
+    }
+    if let Some(snap) = std::env::var_os("SNAP") {
+        roots.push(snap.to_string_lossy().to_string());
+    }
+
+    roots
+}
+
+/// Builds a clean environment map suitable for `Command::env_clear().envs(...)`:
+/// strips bundle-injected library paths when sandboxed, drops empty-valued
+/// variables entirely, and replaces `PATH` with `env::normalized_path()`.
+/// `XDG_*` variables are passed through exactly as this process sees them;
+/// bundle-added entries in them are not removed.
+pub fn normalized_command_env() -> HashMap<String, String> {
+    let mut env = HashMap::new();
+
+    for (key, value) in std::env::vars() {
+        if value.is_empty() {
+            continue;
+        }
+        if is_sandboxed() && BUNDLE_INJECTED_VARS.contains(&key.as_str()) {
+            continue;
+        }
+        env.insert(key, value);
+    }
+
+    env.insert("PATH".to_string(), crate::env::normalized_path());
+    env
+}